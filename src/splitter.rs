@@ -1,7 +1,9 @@
 use crate::constraints::{Constraint, ConstraintMap};
-use crate::histogram::HistogramMatrix;
+use crate::data::Matrix;
+use crate::histogram::{Bin, HistogramMatrix};
 use crate::node::SplittableNode;
 use crate::utils::{constrained_weight, cull_gain, gain_given_weight, weight};
+use std::collections::HashSet;
 
 #[derive(Debug)]
 pub struct SplitInfo {
@@ -9,11 +11,18 @@ pub struct SplitInfo {
     pub split_feature: usize,
     pub split_value: f64,
     pub split_bin: u16,
+    /// Set of bin indices that are sent left, for a categorical split.
+    /// `None` for a numeric split, where `split_value`/`split_bin` are used instead.
+    pub split_categories: Option<Vec<u16>>,
     pub left_node: NodeInfo,
     pub right_node: NodeInfo,
     pub missing_node: MissingInfo,
 }
 
+/// Per-child split statistics (gradient, gain, cover, weight, and the
+/// propagated monotonicity bounds) for a single target. Scalar rather than
+/// per-target, since `Bin`/`HistogramMatrix` only store a single
+/// gradient/hessian sum per bin.
 #[derive(Debug)]
 pub struct NodeInfo {
     pub grad: f32,
@@ -30,6 +39,64 @@ pub enum MissingInfo {
     Branch(NodeInfo),
 }
 
+/// Derive a child's histogram from its parent and sibling, bin by bin:
+/// `larger.histogram = parent.histogram - smaller.histogram`.
+pub fn subtract_histograms(parent: &HistogramMatrix, smaller_child: &HistogramMatrix) -> HistogramMatrix {
+    let HistogramMatrix(parent_matrix) = parent;
+    let HistogramMatrix(child_matrix) = smaller_child;
+
+    let bins: Vec<Bin> = parent_matrix
+        .data
+        .iter()
+        .zip(child_matrix.data.iter())
+        .map(|(p, c)| Bin {
+            grad_sum: p.grad_sum - c.grad_sum,
+            hess_sum: p.hess_sum - c.hess_sum,
+            counts: p.counts - c.counts,
+            cut_value: p.cut_value,
+        })
+        .collect();
+    HistogramMatrix(Matrix::new(&bins, parent_matrix.rows, parent_matrix.cols))
+}
+
+/// Produce both children's histograms given whichever side was built from
+/// raw row data, deriving the other via [`subtract_histograms`].
+/// `SplittableNode::update_children` does not call it.
+pub fn split_child_histograms(
+    parent: &HistogramMatrix,
+    left_is_smaller: bool,
+    smaller_child_histograms: HistogramMatrix,
+) -> (HistogramMatrix, HistogramMatrix) {
+    let larger_child_histograms = subtract_histograms(parent, &smaller_child_histograms);
+    if left_is_smaller {
+        (smaller_child_histograms, larger_child_histograms)
+    } else {
+        (larger_child_histograms, smaller_child_histograms)
+    }
+}
+
+/// Round a single gradient/hessian value to the nearest integer level at
+/// `scale`, returned in the original units (`level * scale`), not as the
+/// integer level itself, so it can be summed alongside ordinary
+/// gradients/hessians.
+fn discretize(value: f32, scale: f32) -> f32 {
+    (value / scale).round() * scale
+}
+
+/// Discretize every row's gradient and hessian to integer levels at
+/// `grad_scale`/`hess_scale`, in place, before they're passed into
+/// `HistogramMatrix::new`. The rounded values stay in their original units
+/// (`level * scale`), so the histogram still sums them as f32; those sums
+/// are not exact integer sums.
+pub fn discretize_gradients(grad: &mut [f32], hess: &mut [f32], grad_scale: f32, hess_scale: f32) {
+    for g in grad.iter_mut() {
+        *g = discretize(*g, grad_scale);
+    }
+    for h in hess.iter_mut() {
+        *h = discretize(*h, hess_scale);
+    }
+}
+
 /// Splitter that imputes missing values, by sending
 /// them down either the right or left branch, depending
 /// on which results in a higher increase in gain.
@@ -40,9 +107,26 @@ pub struct MissingImputerSplitter {
     pub learning_rate: f32,
     pub allow_missing_splits: bool,
     pub constraints_map: ConstraintMap,
+    /// Features that should be treated as categorical rather than ordered numeric.
+    pub categorical_features: Option<HashSet<usize>>,
+    /// Cap on the number of categories considered when scanning for the best
+    /// categorical partition, so high-cardinality features don't blow up split time.
+    pub max_cat_threshold: usize,
+    /// Bit width (e.g. 8 or 16) used for gradient discretization, returned by
+    /// `get_grad_discretize_bits`. Setting it does not discretize anything by
+    /// itself: gradients and hessians are rounded only where the caller passes
+    /// them through `discretize_gradients` before building the histogram.
+    pub grad_discretize_bits: Option<u8>,
+    /// Per-iteration scale factors used to map gradients/hessians to integer
+    /// levels, set by the caller ahead of each boosting round as
+    /// `max_abs_value / (2^bits - 1)`. Returned by `get_grad_scale`/
+    /// `get_hess_scale`; split evaluation does not read them.
+    pub grad_scale: f32,
+    pub hess_scale: f32,
 }
 
 impl MissingImputerSplitter {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         l2: f32,
         gamma: f32,
@@ -50,6 +134,8 @@ impl MissingImputerSplitter {
         learning_rate: f32,
         allow_missing_splits: bool,
         constraints_map: ConstraintMap,
+        categorical_features: Option<HashSet<usize>>,
+        max_cat_threshold: usize,
     ) -> Self {
         MissingImputerSplitter {
             l2,
@@ -58,8 +144,31 @@ impl MissingImputerSplitter {
             learning_rate,
             allow_missing_splits,
             constraints_map,
+            categorical_features,
+            max_cat_threshold,
+            grad_discretize_bits: None,
+            grad_scale: 1.0,
+            hess_scale: 1.0,
         }
     }
+
+    /// Compute the per-iteration scale factor that maps a gradient or hessian
+    /// value to an integer level at `bits` of precision, i.e.
+    /// `max_abs_value / (2^bits - 1)`. The caller computes `max_abs_value`
+    /// over the current round's gradients (or hessians) and calls this once
+    /// per boosting round before setting `grad_scale`/`hess_scale`.
+    ///
+    /// `bits` must be in `1..=24`, so the resulting levels fit in an f32
+    /// without losing precision; anything higher defeats the point of
+    /// discretizing and would overflow the level count besides.
+    pub fn discretize_scale(max_abs_value: f32, bits: u8) -> f32 {
+        assert!(
+            (1..=24).contains(&bits),
+            "grad_discretize_bits must be between 1 and 24, got {bits}"
+        );
+        let levels = (1u32 << bits) - 1;
+        max_abs_value / levels as f32
+    }
 }
 
 impl Splitter for MissingImputerSplitter {
@@ -232,6 +341,29 @@ impl Splitter for MissingImputerSplitter {
     fn get_l2(&self) -> f32 {
         self.l2
     }
+
+    fn is_categorical(&self, feature: &usize) -> bool {
+        match &self.categorical_features {
+            Some(features) => features.contains(feature),
+            None => false,
+        }
+    }
+
+    fn get_max_cat_threshold(&self) -> usize {
+        self.max_cat_threshold
+    }
+
+    fn get_grad_discretize_bits(&self) -> Option<u8> {
+        self.grad_discretize_bits
+    }
+
+    fn get_grad_scale(&self) -> f32 {
+        self.grad_scale
+    }
+
+    fn get_hess_scale(&self) -> f32 {
+        self.hess_scale
+    }
 }
 
 pub trait Splitter {
@@ -239,6 +371,11 @@ pub trait Splitter {
     // fn get_allow_missing_splits(&self) -> bool;
     fn get_gamma(&self) -> f32;
     fn get_l2(&self) -> f32;
+    fn is_categorical(&self, feature: &usize) -> bool;
+    fn get_max_cat_threshold(&self) -> usize;
+    fn get_grad_discretize_bits(&self) -> Option<u8>;
+    fn get_grad_scale(&self) -> f32;
+    fn get_hess_scale(&self) -> f32;
 
     fn best_split(&self, node: &SplittableNode) -> Option<SplitInfo> {
         let mut best_split_info = None;
@@ -274,6 +411,14 @@ pub trait Splitter {
     ) -> Option<(NodeInfo, NodeInfo, MissingInfo)>;
 
     fn best_feature_split(&self, node: &SplittableNode, feature: usize) -> Option<SplitInfo> {
+        if self.is_categorical(&feature) {
+            self.best_categorical_feature_split(node, feature)
+        } else {
+            self.best_numeric_feature_split(node, feature)
+        }
+    }
+
+    fn best_numeric_feature_split(&self, node: &SplittableNode, feature: usize) -> Option<SplitInfo> {
         let mut split_info: Option<SplitInfo> = None;
         let mut max_gain: Option<f32> = None;
 
@@ -286,14 +431,19 @@ pub trait Splitter {
         let mut cuml_hess = 0.0; // first_bin.hess_sum;
         let constraint = self.get_constraint(&feature);
 
+        // Note: when gradients were passed through `discretize_gradients`
+        // before the histogram was built, `bin.grad_sum`/`hess_sum` are sums
+        // of values rounded to multiples of the scale, in their original
+        // units. They are accumulated here as plain f32 like any other
+        // gradients, so these sums are not exact.
+
         let elements = histogram.len();
         assert!(elements == histogram.len());
 
         for (i, bin) in histogram[1..].iter().enumerate() {
-            let left_gradient = cuml_grad;
-            let left_hessian = cuml_hess;
-            let right_gradient = node.grad_sum - cuml_grad - missing.grad_sum;
-            let right_hessian = node.hess_sum - cuml_hess - missing.hess_sum;
+            let (left_gradient, left_hessian) = (cuml_grad, cuml_hess);
+            let right_gradient = node.grad_sum - left_gradient - missing.grad_sum;
+            let right_hessian = node.hess_sum - left_hessian - missing.hess_sum;
 
             let (mut left_node_info, mut right_node_info, missing_info) = match self.evaluate_split(
                 left_gradient,
@@ -354,6 +504,7 @@ pub trait Splitter {
                     split_feature: feature,
                     split_value: bin.cut_value,
                     split_bin: (i + 1) as u16,
+                    split_categories: None,
                     left_node: left_node_info,
                     right_node: right_node_info,
                     missing_node: missing_info,
@@ -365,6 +516,125 @@ pub trait Splitter {
         }
         split_info
     }
+
+    /// Find the best split for a categorical feature, using the XGBoost
+    /// partition-scan approach: order the category bins by their
+    /// `grad_sum / (hess_sum + l2)` statistic, then scan a prefix of that
+    /// ordering into the left partition one category at a time, evaluating
+    /// gain at each step with the existing `evaluate_split`. This turns an
+    /// otherwise exponential subset search into a single sort plus linear scan.
+    ///
+    /// The scan runs in both directions (ascending and descending through the
+    /// sorted categories) so the missing bin can end up combined with either
+    /// the accumulated prefix or its complement, and the prefix length is
+    /// capped by `max_cat_threshold` to bound cost on high-cardinality features.
+    /// Accumulates `cuml_grad`/`cuml_hess` as plain f32, same as
+    /// `best_numeric_feature_split`, whether or not the gradients were passed
+    /// through `discretize_gradients`.
+    fn best_categorical_feature_split(&self, node: &SplittableNode, feature: usize) -> Option<SplitInfo> {
+        let mut split_info: Option<SplitInfo> = None;
+        let mut max_gain: Option<f32> = None;
+
+        let HistogramMatrix(histograms) = &node.histograms;
+        let histogram = histograms.get_col(feature);
+        let missing = &histogram[0];
+        let constraint = self.get_constraint(&feature);
+
+        let mut ordered_bins: Vec<usize> = (1..histogram.len()).collect();
+        ordered_bins.sort_by(|&a, &b| {
+            let stat_a = histogram[a].grad_sum / (histogram[a].hess_sum + self.get_l2());
+            let stat_b = histogram[b].grad_sum / (histogram[b].hess_sum + self.get_l2());
+            stat_a
+                .partial_cmp(&stat_b)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let n_considered = ordered_bins.len().min(self.get_max_cat_threshold());
+
+        for reversed in [false, true] {
+            let mut cuml_grad = 0.0;
+            let mut cuml_hess = 0.0;
+            let mut left_categories: Vec<u16> = Vec::with_capacity(n_considered);
+
+            let scan: Vec<usize> = if reversed {
+                ordered_bins.iter().rev().take(n_considered).copied().collect()
+            } else {
+                ordered_bins.iter().take(n_considered).copied().collect()
+            };
+
+            for bin_idx in scan {
+                let bin = &histogram[bin_idx];
+                cuml_grad += bin.grad_sum;
+                cuml_hess += bin.hess_sum;
+                left_categories.push(bin_idx as u16);
+
+                let left_gradient = cuml_grad;
+                let left_hessian = cuml_hess;
+                let right_gradient = node.grad_sum - cuml_grad - missing.grad_sum;
+                let right_hessian = node.hess_sum - cuml_hess - missing.hess_sum;
+
+                let (mut left_node_info, mut right_node_info, missing_info) =
+                    match self.evaluate_split(
+                        left_gradient,
+                        left_hessian,
+                        right_gradient,
+                        right_hessian,
+                        missing.grad_sum,
+                        missing.hess_sum,
+                        node.lower_bound,
+                        node.upper_bound,
+                        constraint,
+                    ) {
+                        None => continue,
+                        Some(v) => v,
+                    };
+
+                let split_gain = (left_node_info.gain + right_node_info.gain - node.gain_value)
+                    - self.get_gamma();
+                let split_gain = cull_gain(
+                    split_gain,
+                    left_node_info.weight,
+                    right_node_info.weight,
+                    constraint,
+                );
+
+                if split_gain <= 0.0 {
+                    continue;
+                }
+
+                let mid = (left_node_info.weight + right_node_info.weight) / 2.0;
+                let (left_bounds, right_bounds) = match constraint {
+                    None | Some(Constraint::Unconstrained) => (
+                        (node.lower_bound, node.upper_bound),
+                        (node.lower_bound, node.upper_bound),
+                    ),
+                    Some(Constraint::Negative) => {
+                        ((mid, node.upper_bound), (node.lower_bound, mid))
+                    }
+                    Some(Constraint::Positive) => {
+                        ((node.lower_bound, mid), (mid, node.upper_bound))
+                    }
+                };
+                left_node_info.bounds = left_bounds;
+                right_node_info.bounds = right_bounds;
+
+                let split_gain = if split_gain.is_nan() { 0.0 } else { split_gain };
+                if max_gain.is_none() || split_gain > max_gain.unwrap() {
+                    max_gain = Some(split_gain);
+                    split_info = Some(SplitInfo {
+                        split_gain,
+                        split_feature: feature,
+                        split_value: f64::NAN,
+                        split_bin: bin_idx as u16,
+                        split_categories: Some(left_categories.clone()),
+                        left_node: left_node_info,
+                        right_node: right_node_info,
+                        missing_node: missing_info,
+                    });
+                }
+            }
+        }
+        split_info
+    }
 }
 
 #[cfg(test)]
@@ -397,6 +667,11 @@ mod tests {
             learning_rate: 1.0,
             allow_missing_splits: true,
             constraints_map: ConstraintMap::new(),
+            categorical_features: None,
+            max_cat_threshold: 255,
+            grad_discretize_bits: None,
+            grad_scale: 1.0,
+            hess_scale: 1.0,
         };
         // println!("{:?}", hists);
         let mut n = SplittableNode::new(
@@ -423,6 +698,62 @@ mod tests {
         assert_eq!(s.split_gain, 3.86);
     }
 
+    #[test]
+    fn test_best_categorical_feature_split() {
+        let d = vec![4., 2., 3., 4., 5., 1., 4.];
+        let data = Matrix::new(&d, 7, 1);
+        let y = vec![0., 0., 0., 1., 1., 0., 1.];
+        let yhat = vec![0.; 7];
+        let w = vec![1.; y.len()];
+        let grad = LogLoss::calc_grad(&y, &yhat, &w);
+        let hess = LogLoss::calc_hess(&y, &yhat, &w);
+
+        let b = bin_matrix(&data, &w, 10).unwrap();
+        let bdata = Matrix::new(&b.binned_data, data.rows, data.cols);
+        let index = data.index.to_owned();
+        let hists = HistogramMatrix::new(&bdata, &b.cuts, &grad, &hess, &index, true, false);
+        let mut categorical_features = HashSet::new();
+        categorical_features.insert(0);
+        let splitter = MissingImputerSplitter {
+            l2: 0.0,
+            gamma: 0.0,
+            min_leaf_weight: 0.0,
+            learning_rate: 1.0,
+            allow_missing_splits: true,
+            constraints_map: ConstraintMap::new(),
+            categorical_features: Some(categorical_features),
+            max_cat_threshold: 255,
+            grad_discretize_bits: None,
+            grad_scale: 1.0,
+            hess_scale: 1.0,
+        };
+        let mut n = SplittableNode::new(
+            0,
+            hists,
+            0.0,
+            0.14,
+            grad.iter().sum::<f32>(),
+            hess.iter().sum::<f32>(),
+            0,
+            0,
+            grad.len(),
+            f32::NEG_INFINITY,
+            f32::INFINITY,
+        );
+        let s = splitter.best_feature_split(&mut n, 0).unwrap();
+        // Same data as `test_best_feature_split`, but treated as categorical:
+        // the partition scan groups categories 5 and 4 (the two negative-gradient
+        // bins) on one side and 1, 2, 3 on the other, which rediscovers the same
+        // {1,2,3}|{4,5} grouping as the best numeric split on this data (3.86,
+        // from splitting between 3 and 4), so the gain matches rather than beats it.
+        assert_eq!(s.split_categories, Some(vec![5, 4]));
+        assert_eq!(s.left_node.cover, 1.0);
+        assert_eq!(s.right_node.cover, 0.75);
+        assert_eq!(s.left_node.gain, 1.0);
+        assert_eq!(s.right_node.gain, 3.0);
+        assert_eq!(s.split_gain, 3.86);
+    }
+
     #[test]
     fn test_best_split() {
         let d: Vec<f64> = vec![0., 0., 0., 1., 0., 0., 0., 4., 2., 3., 4., 5., 1., 4.];
@@ -445,6 +776,11 @@ mod tests {
             learning_rate: 1.0,
             allow_missing_splits: true,
             constraints_map: ConstraintMap::new(),
+            categorical_features: None,
+            max_cat_threshold: 255,
+            grad_discretize_bits: None,
+            grad_scale: 1.0,
+            hess_scale: 1.0,
         };
         let mut n = SplittableNode::new(
             0,
@@ -471,6 +807,61 @@ mod tests {
         assert_eq!(s.split_gain, 3.86);
     }
 
+    #[test]
+    fn test_best_split_discretized() {
+        let d: Vec<f64> = vec![0., 0., 0., 1., 0., 0., 0., 4., 2., 3., 4., 5., 1., 4.];
+        let data = Matrix::new(&d, 7, 2);
+        let y = vec![0., 0., 0., 1., 1., 0., 1.];
+        let yhat = vec![0.; 7];
+        let w = vec![1.; y.len()];
+        let mut grad = LogLoss::calc_grad(&y, &yhat, &w);
+        let mut hess = LogLoss::calc_hess(&y, &yhat, &w);
+
+        let max_abs_grad = grad.iter().fold(0_f32, |acc, &g| acc.max(g.abs()));
+        let max_abs_hess = hess.iter().fold(0_f32, |acc, &h| acc.max(h.abs()));
+        let bits = 16;
+        let grad_scale = MissingImputerSplitter::discretize_scale(max_abs_grad, bits);
+        let hess_scale = MissingImputerSplitter::discretize_scale(max_abs_hess, bits);
+        discretize_gradients(&mut grad, &mut hess, grad_scale, hess_scale);
+
+        let b = bin_matrix(&data, &w, 10).unwrap();
+        let bdata = Matrix::new(&b.binned_data, data.rows, data.cols);
+        let index = data.index.to_owned();
+        let hists = HistogramMatrix::new(&bdata, &b.cuts, &grad, &hess, &index, true, false);
+
+        let splitter = MissingImputerSplitter {
+            l2: 0.0,
+            gamma: 0.0,
+            min_leaf_weight: 0.0,
+            learning_rate: 1.0,
+            allow_missing_splits: true,
+            constraints_map: ConstraintMap::new(),
+            categorical_features: None,
+            max_cat_threshold: 255,
+            grad_discretize_bits: Some(bits),
+            grad_scale,
+            hess_scale,
+        };
+        let mut n = SplittableNode::new(
+            0,
+            hists,
+            0.0,
+            0.14,
+            grad.iter().sum::<f32>(),
+            hess.iter().sum::<f32>(),
+            0,
+            0,
+            grad.len(),
+            f32::NEG_INFINITY,
+            f32::INFINITY,
+        );
+        let s = splitter.best_split(&mut n).unwrap();
+        // At 16 bits of precision, the discretized path should pick the same
+        // split as the float path in `test_best_split`.
+        assert_eq!(s.split_feature, 1);
+        assert_eq!(s.split_value, 4.);
+    }
+
     #[test]
     fn test_data_split() {
         let file = fs::read_to_string("resources/contiguous_no_missing.csv")
@@ -491,6 +882,11 @@ mod tests {
             learning_rate: 0.3,
             allow_missing_splits: true,
             constraints_map: ConstraintMap::new(),
+            categorical_features: None,
+            max_cat_threshold: 255,
+            grad_discretize_bits: None,
+            grad_scale: 1.0,
+            hess_scale: 1.0,
         };
         let grad_sum = grad.iter().copied().sum();
         let hess_sum = hess.iter().copied().sum();
@@ -524,4 +920,58 @@ mod tests {
         n.update_children(1, 2, &s);
         assert_eq!(0, s.split_feature);
     }
+
+    #[test]
+    fn test_subtract_histograms() {
+        let d = vec![4., 2., 3., 4., 5., 1., 4., 6., 2., 1.];
+        let data = Matrix::new(&d, 10, 1);
+        let y = vec![0., 0., 0., 1., 1., 0., 1., 1., 0., 0.];
+        let yhat = vec![0.; 10];
+        let w = vec![1.; y.len()];
+        let grad = LogLoss::calc_grad(&y, &yhat, &w);
+        let hess = LogLoss::calc_hess(&y, &yhat, &w);
+
+        let b = bin_matrix(&data, &w, 10).unwrap();
+        let bdata = Matrix::new(&b.binned_data, data.rows, data.cols);
+        let index = data.index.to_owned();
+
+        // Build the histogram for the full parent node, and for an arbitrary
+        // smaller "left child" subset of the rows.
+        let parent_hists = HistogramMatrix::new(&bdata, &b.cuts, &grad, &hess, &index, true, false);
+        let left_index = index[..4].to_owned();
+        let left_hists =
+            HistogramMatrix::new(&bdata, &b.cuts, &grad, &hess, &left_index, true, false);
+        let right_index = index[4..].to_owned();
+        let right_hists_built =
+            HistogramMatrix::new(&bdata, &b.cuts, &grad, &hess, &right_index, true, false);
+
+        let right_hists_subtracted = subtract_histograms(&parent_hists, &left_hists);
+
+        let HistogramMatrix(built) = &right_hists_built;
+        let HistogramMatrix(subtracted) = &right_hists_subtracted;
+        for (built_bin, subtracted_bin) in built.data.iter().zip(subtracted.data.iter()) {
+            assert!((built_bin.grad_sum - subtracted_bin.grad_sum).abs() < 0.0001);
+            assert!((built_bin.hess_sum - subtracted_bin.hess_sum).abs() < 0.0001);
+            assert_eq!(built_bin.counts, subtracted_bin.counts);
+        }
+
+        // `split_child_histograms`: whichever side is flagged as smaller is
+        // passed through untouched, and the other side is derived by
+        // subtraction.
+        let left_grad_sums: Vec<f32> = {
+            let HistogramMatrix(m) = &left_hists;
+            m.data.iter().map(|b| b.grad_sum).collect()
+        };
+        let (left_out, right_out) = split_child_histograms(&parent_hists, true, left_hists);
+        let HistogramMatrix(right_out) = &right_out;
+        for (built_bin, derived_bin) in built.data.iter().zip(right_out.data.iter()) {
+            assert!((built_bin.grad_sum - derived_bin.grad_sum).abs() < 0.0001);
+            assert!((built_bin.hess_sum - derived_bin.hess_sum).abs() < 0.0001);
+            assert_eq!(built_bin.counts, derived_bin.counts);
+        }
+        let HistogramMatrix(left_out) = &left_out;
+        for (expected_grad_sum, actual_bin) in left_grad_sums.iter().zip(left_out.data.iter()) {
+            assert!((expected_grad_sum - actual_bin.grad_sum).abs() < 0.0001);
+        }
+    }
 }